@@ -1,5 +1,5 @@
 use std::{convert::TryFrom, str::FromStr, fmt::Display};
-use crate::cpf::CPFDocument;
+use crate::cpf::{CPFDocument, FiscalRegion};
 use crate::{DocumentValidator, ErrorKind};
 use crate::cnpj::CNPJDocument;
 use crate::br_document::DocumentNumber::{CNPJ, CPF};
@@ -15,11 +15,11 @@ impl TryFrom<String> for DocumentNumber {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         CPFDocument::try_from(value.clone())
-            .map(|it| CPF(it))
+            .map(CPF)
             .or_else(|error| {
                 match error {
-                    ErrorKind::InvalidCharacters | ErrorKind::InvalidDocument => Err(error),
-                    ErrorKind::InvalidInput => Ok(CNPJ(CNPJDocument::try_from(value)?))
+                    ErrorKind::InvalidInput => Ok(CNPJ(CNPJDocument::try_from(value)?)),
+                    _ => Err(error),
                 }
             })
     }
@@ -42,41 +42,178 @@ impl Display for DocumentNumber {
     }
 }
 
+impl DocumentNumber {
+    /// Returns the document formatted with its canonical mask (CPF or CNPJ).
+    pub fn formatted(&self) -> String {
+        match self {
+            CPF(document) => document.formatted(),
+            CNPJ(document) => document.formatted(),
+        }
+    }
+
+    /// Returns the document's bare digits, stripped of any mask.
+    pub fn digits(&self) -> String {
+        match self {
+            CPF(document) => document.digits(),
+            CNPJ(document) => document.digits(),
+        }
+    }
+
+    /// Returns the fiscal region the document's CPF was issued under, or `None` for a CNPJ.
+    pub fn region(&self) -> Option<FiscalRegion> {
+        match self {
+            CPF(document) => Some(document.fiscal_region()),
+            CNPJ(_) => None,
+        }
+    }
+
+    /// Generates a [`DocumentNumber::CPF`] wrapping a random, structurally valid CPF.
+    ///
+    /// Pass `Some(seed)` to make the generation deterministic, which is useful for
+    /// seeding tests and fixtures.
+    pub fn generate_cpf(seed: Option<u64>) -> Self {
+        CPF(CPFDocument::generate(seed))
+    }
+
+    /// Generates a [`DocumentNumber::CNPJ`] wrapping a random, structurally valid CNPJ.
+    ///
+    /// Pass `Some(seed)` to make the generation deterministic, which is useful for
+    /// seeding tests and fixtures.
+    pub fn generate_cnpj(seed: Option<u64>) -> Self {
+        CNPJ(CNPJDocument::generate(seed))
+    }
+}
+
 impl DocumentValidator for DocumentNumber {
     type Error = ErrorKind;
 
     fn validate_input(input: &str) -> Option<Self::Error> {
-        if let Some(error) = CPFDocument::validate_input(input) {
-            return match error {
-                ErrorKind::InvalidCharacters | ErrorKind::InvalidDocument => Some(error),
-                ErrorKind::InvalidInput => CNPJDocument::validate_input(input)
-            }
+        match CPFDocument::validate_input(input) {
+            Some(ErrorKind::InvalidInput) => CNPJDocument::validate_input(input),
+            other => other,
         }
-
-        None
     }
 }
 
+/// Validates many documents at once, returning one result per input in the same order.
+///
+/// Enable the `rayon` feature to validate the batch in parallel, which is useful when
+/// cleaning spreadsheet or CSV columns with millions of documents, since check-digit
+/// validation is pure and embarrassingly parallel.
+#[cfg(not(feature = "rayon"))]
+pub fn validate_many(inputs: &[&str]) -> Vec<Result<DocumentNumber, ErrorKind>> {
+    inputs.iter().map(|input| DocumentNumber::try_from(input.to_string())).collect()
+}
+
+/// Validates many documents at once, in parallel, returning one result per input in the
+/// same order.
+#[cfg(feature = "rayon")]
+pub fn validate_many(inputs: &[&str]) -> Vec<Result<DocumentNumber, ErrorKind>> {
+    use rayon::prelude::*;
+
+    inputs.par_iter().map(|input| DocumentNumber::try_from(input.to_string())).collect()
+}
+
+/// Returns `true` if `input` is a structurally and numerically valid CPF.
+pub fn is_valid_cpf(input: &str) -> bool {
+    CPFDocument::validate_input(input).is_none()
+}
+
+/// Returns `true` if `input` is a structurally and numerically valid CNPJ.
+pub fn is_valid_cnpj(input: &str) -> bool {
+    CNPJDocument::validate_input(input).is_none()
+}
+
+/// Returns `true` if `input` is a structurally and numerically valid CPF or CNPJ.
+pub fn is_valid_document(input: &str) -> bool {
+    DocumentNumber::validate_input(input).is_none()
+}
+
 #[cfg(test)]
 mod tests {
     use super::{DocumentNumber, ErrorKind};
+    use crate::InvalidDocumentReason;
     use test_case::test_case;
     use crate::DocumentValidator;
 
-    #[test_case("96865090039", None                                     ; "Valid CPFs should be allowed")]
-    #[test_case("03165685000114", None                                  ; "Valid CNPJ should be allowed")]
-    #[test_case("11111111111", Some(ErrorKind::InvalidDocument)         ; "All equal characters CPF should not be allowed")]
-    #[test_case("79888245131", Some(ErrorKind::InvalidDocument)         ; "CPF with incorrect verification digits should not be allowed")]
-    #[test_case("73361907000130", Some(ErrorKind::InvalidDocument)      ; "CNPJ with incorrect verification digits should not be allowed")]
-    #[test_case("272676S6021", Some(ErrorKind::InvalidCharacters)       ; "CPF with invalid characters should not be allowed")]
-    #[test_case("896S4922000126", Some(ErrorKind::InvalidCharacters)    ; "CNPJ with invalid characters should not be allowed")]
-    #[test_case("6611493500107", Some(ErrorKind::InvalidInput)          ; "Input with incorrect character count should not be allowed - 1")]
-    #[test_case("2881121027", Some(ErrorKind::InvalidInput)             ; "Input with incorrect character count should not be allowed - 2")]
-    #[test_case("661149350000107", Some(ErrorKind::InvalidInput)        ; "Input with incorrect character count should not be allowed - 3")]
-    #[test_case("288111221027", Some(ErrorKind::InvalidInput)           ; "Input with incorrect character count should not be allowed - 4")]
+    #[test_case("96865090039", None                                                                                                  ; "Valid CPFs should be allowed")]
+    #[test_case("03165685000114", None                                                                                               ; "Valid CNPJ should be allowed")]
+    #[test_case("968.650.900-39", None                                                                                               ; "Masked CPF should be allowed")]
+    #[test_case("03.165.685/0001-14", None                                                                                           ; "Masked CNPJ should be allowed")]
+    #[test_case("11111111111", Some(ErrorKind::InvalidDocument(InvalidDocumentReason::AllDigitsEqual))                               ; "All equal characters CPF should not be allowed")]
+    #[test_case("111.111.111-11", Some(ErrorKind::InvalidDocument(InvalidDocumentReason::AllDigitsEqual))                            ; "Masked all equal characters CPF should not be allowed")]
+    #[test_case("79888245131", Some(ErrorKind::InvalidDocument(InvalidDocumentReason::CheckDigitMismatch { expected: (1, 2), actual: (3, 1) }))      ; "CPF with incorrect verification digits should not be allowed")]
+    #[test_case("73361907000130", Some(ErrorKind::InvalidDocument(InvalidDocumentReason::CheckDigitMismatch { expected: (5, 0), actual: (3, 0) }))   ; "CNPJ with incorrect verification digits should not be allowed")]
+    #[test_case("272676S6021", Some(ErrorKind::InvalidCharacters { index: 6, character: 'S' })                                       ; "CPF with invalid characters should not be allowed")]
+    #[test_case("896S4922000126", Some(ErrorKind::InvalidCharacters { index: 3, character: 'S' })                                    ; "CNPJ with invalid characters should not be allowed")]
+    #[test_case("272.676.S60-21", Some(ErrorKind::InvalidCharacters { index: 8, character: 'S' })                                    ; "Masked CPF with invalid characters should not be allowed, pointing at the real offender rather than a mask separator")]
+    #[test_case("66.114-935/0001-07", Some(ErrorKind::InvalidInput)                                                                  ; "CNPJ with invalid mask grouping should not be allowed")]
+    #[test_case("6611493500107", Some(ErrorKind::InvalidInput)                                                                       ; "Input with incorrect character count should not be allowed - 1")]
+    #[test_case("2881121027", Some(ErrorKind::InvalidInput)                                                                          ; "Input with incorrect character count should not be allowed - 2")]
+    #[test_case("661149350000107", Some(ErrorKind::InvalidInput)                                                                     ; "Input with incorrect character count should not be allowed - 3")]
+    #[test_case("288111221027", Some(ErrorKind::InvalidInput)                                                                        ; "Input with incorrect character count should not be allowed - 4")]
+    #[test_case("", Some(ErrorKind::Empty)                                                                                           ; "Empty input should not be allowed")]
     fn validate(input: &str, expected: Option<ErrorKind>) {
-        let actual = DocumentNumber::validate_input(&input);
+        let actual = DocumentNumber::validate_input(input);
 
         assert_eq!(actual, expected);
     }
+
+    #[test_case("96865090039", true   ; "Valid CPF is valid")]
+    #[test_case("03165685000114", false ; "CNPJ is not a valid CPF")]
+    #[test_case("11111111111", false  ; "All equal characters CPF is not valid")]
+    fn is_valid_cpf(input: &str, expected: bool) {
+        assert_eq!(super::is_valid_cpf(input), expected);
+    }
+
+    #[test_case("03165685000114", true  ; "Valid CNPJ is valid")]
+    #[test_case("96865090039", false    ; "CPF is not a valid CNPJ")]
+    fn is_valid_cnpj(input: &str, expected: bool) {
+        assert_eq!(super::is_valid_cnpj(input), expected);
+    }
+
+    #[test_case("96865090039", true      ; "Valid CPF is a valid document")]
+    #[test_case("03165685000114", true   ; "Valid CNPJ is a valid document")]
+    #[test_case("", false                ; "Empty input is not a valid document")]
+    fn is_valid_document(input: &str, expected: bool) {
+        assert_eq!(super::is_valid_document(input), expected);
+    }
+
+    #[test]
+    fn validate_many_preserves_order_and_reports_each_result() {
+        let inputs = ["96865090039", "03165685000114", ""];
+
+        let results = super::validate_many(&inputs);
+
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert_eq!(results[2], Err(ErrorKind::Empty));
+    }
+
+    /// Only compiled with `--features rayon`, so the parallel `validate_many` arm gets its own
+    /// run instead of being silently shadowed by the serial build that normally runs this suite.
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn validate_many_preserves_order_across_a_larger_parallel_batch() {
+        let inputs: Vec<&str> = (0..64)
+            .map(|i| if i % 2 == 0 { "96865090039" } else { "03165685000114" })
+            .collect();
+
+        let results = super::validate_many(&inputs);
+
+        for (index, result) in results.iter().enumerate() {
+            match result {
+                Ok(DocumentNumber::CPF(_)) => assert_eq!(index % 2, 0, "index {index} should be a CPF"),
+                Ok(DocumentNumber::CNPJ(_)) => assert_eq!(index % 2, 1, "index {index} should be a CNPJ"),
+                Err(error) => panic!("input at index {index} should be valid, got {error:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn region_is_none_for_a_cnpj() {
+        let document = DocumentNumber::try_from("03165685000114".to_string()).unwrap();
+
+        assert_eq!(document.region(), None);
+    }
 }
@@ -1,8 +1,17 @@
 use std::fmt::Display;
-use crate::{all_equal, DocumentValidator, ErrorKind, to_integer_vector};
+use nom::branch::alt;
+use nom::combinator::{all_consuming, map};
+use nom::sequence::tuple;
+use nom::IResult;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::parsing::{classify_failure, digit_group, separator};
+use crate::{all_equal, numbers_to_string, DocumentValidator, ErrorKind, InvalidDocumentReason};
 
 const CNPJ_POSITIONAL_WEIGHTS: [u32; 13] = [6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
 const CNPJ_SIZE: usize = 14;
+const CNPJ_MASKED_SIZE: usize = 18;
+const CNPJ_MASK_SEPARATORS: [char; 3] = ['.', '/', '-'];
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CNPJDocument(String);
@@ -11,10 +20,7 @@ impl TryFrom<String> for CNPJDocument {
     type Error = ErrorKind;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        CNPJDocument::validate_input(&value)
-            .map_or(
-                Ok(CNPJDocument(value.to_string())),
-                |err| Err(err))
+        parse(&value).map(|digits| CNPJDocument(numbers_to_string(&digits)))
     }
 }
 
@@ -22,20 +28,50 @@ impl DocumentValidator for CNPJDocument {
     type Error = ErrorKind;
 
     fn validate_input(input: &str) -> Option<Self::Error> {
-        if input.len() != CNPJ_SIZE {
-            return Some(ErrorKind::InvalidInput)
-        }
+        parse(input).err()
+    }
+}
 
-        let input_as_integer_vector = to_integer_vector(input);
+/// Parses a CNPJ, masked (`dd.ddd.ddd/dddd-dd`) or bare, returning its digits once they've
+/// passed the all-equal and check-digit validation.
+fn parse(input: &str) -> Result<Vec<u32>, ErrorKind> {
+    if input.is_empty() {
+        return Err(ErrorKind::Empty);
+    }
 
-        if input_as_integer_vector.len() != CNPJ_SIZE {
-            return Some(ErrorKind::InvalidCharacters)
-        }
-        if all_equal(&input_as_integer_vector) {
-            return Some(ErrorKind::InvalidDocument)
-        }
+    let digits = parse_digits(input)?;
+
+    if all_equal(&digits) {
+        return Err(ErrorKind::InvalidDocument(InvalidDocumentReason::AllDigitsEqual));
+    }
+
+    match validate_cnpj(&digits) {
+        Some(error) => Err(error),
+        None => Ok(digits),
+    }
+}
+
+fn cnpj_masked(input: &str) -> IResult<&str, Vec<char>> {
+    map(
+        tuple((
+            digit_group(2), separator('.'),
+            digit_group(3), separator('.'),
+            digit_group(3), separator('/'),
+            digit_group(4), separator('-'),
+            digit_group(2),
+        )),
+        |(a, _, b, _, c, _, d, _, e)| [a, b, c, d, e].concat(),
+    )(input)
+}
+
+fn cnpj_bare(input: &str) -> IResult<&str, Vec<char>> {
+    digit_group(CNPJ_SIZE)(input)
+}
 
-        validate_cnpj(&input_as_integer_vector)
+fn parse_digits(input: &str) -> Result<Vec<u32>, ErrorKind> {
+    match all_consuming(alt((cnpj_masked, cnpj_bare)))(input) {
+        Ok((_, chars)) => Ok(chars.into_iter().map(|c| c.to_digit(10).unwrap()).collect()),
+        Err(_) => Err(classify_failure(input, &CNPJ_MASK_SEPARATORS, &[CNPJ_SIZE, CNPJ_MASKED_SIZE])),
     }
 }
 
@@ -45,15 +81,66 @@ impl Display for CNPJDocument {
     }
 }
 
-fn validate_cnpj(numbers: &Vec<u32>) -> Option<ErrorKind> {
+impl CNPJDocument {
+    /// Returns the document formatted with the canonical CNPJ mask, `dd.ddd.ddd/dddd-dd`.
+    pub fn formatted(&self) -> String {
+        format(&self.0)
+    }
+
+    /// Returns the document's bare digits, stripped of any mask.
+    pub fn digits(&self) -> String {
+        self.0.clone()
+    }
+
+    /// Generates a random, structurally valid CNPJ.
+    ///
+    /// Pass `Some(seed)` to make the generation deterministic, which is useful for
+    /// seeding tests and fixtures.
+    pub fn generate(seed: Option<u64>) -> Self {
+        let mut rng = seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+
+        let base = loop {
+            let candidate: Vec<u32> = (0..12).map(|_| rng.gen_range(0..10)).collect();
+
+            if !all_equal(&candidate) {
+                break candidate;
+            }
+        };
+
+        let (first_digit, second_digit) = calculate_check_digits(&base);
+        let digits: Vec<u32> = base.into_iter().chain([first_digit, second_digit]).collect();
+
+        CNPJDocument(numbers_to_string(&digits))
+    }
+}
+
+/// Progressively masks a partial CNPJ digit string as the user types it,
+/// e.g. `"1234567"` becomes `"12.345.67"`.
+pub fn format(partial: &str) -> String {
+    let digits: String = partial.chars().filter(|c| c.is_ascii_digit()).take(CNPJ_SIZE).collect();
+    let mut result = String::with_capacity(18);
+
+    for (index, character) in digits.chars().enumerate() {
+        match index {
+            2 | 5 => result.push('.'),
+            8 => result.push('/'),
+            12 => result.push('-'),
+            _ => {}
+        }
+        result.push(character);
+    }
+
+    result
+}
+
+fn calculate_check_digits(first_twelve_digits: &[u32]) -> (u32, u32) {
     fn calculate_digit(subject: u32) -> u32 {
         match subject % 11 {
             r if r < 2 => 0,
-            r @ _ => 11 - r,
+            r => 11 - r,
         }
     }
 
-    let first_twelve_digits = &numbers[..12];
     let first_zipped_sum: u32 = first_twelve_digits
         .iter()
         .zip(&CNPJ_POSITIONAL_WEIGHTS[1..])
@@ -71,9 +158,59 @@ fn validate_cnpj(numbers: &Vec<u32>) -> Option<ErrorKind> {
 
     let second_digit = calculate_digit(second_zipped_sum);
 
-    if numbers[numbers.len() - 2] == first_digit && numbers[numbers.len() - 1] == second_digit {
+    (first_digit, second_digit)
+}
+
+fn validate_cnpj(numbers: &[u32]) -> Option<ErrorKind> {
+    let (expected_first_digit, expected_second_digit) = calculate_check_digits(&numbers[..12]);
+    let actual_first_digit = numbers[numbers.len() - 2];
+    let actual_second_digit = numbers[numbers.len() - 1];
+
+    if actual_first_digit == expected_first_digit && actual_second_digit == expected_second_digit {
         None
     } else {
-        Some(ErrorKind::InvalidDocument)
+        Some(ErrorKind::InvalidDocument(InvalidDocumentReason::CheckDigitMismatch {
+            expected: (expected_first_digit, expected_second_digit),
+            actual: (actual_first_digit, actual_second_digit),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CNPJDocument;
+    use crate::br_document::is_valid_cnpj;
+    use crate::DocumentValidator;
+    use test_case::test_case;
+
+    #[test]
+    fn generate_with_the_same_seed_is_deterministic() {
+        let first = CNPJDocument::generate(Some(42));
+        let second = CNPJDocument::generate(Some(42));
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn generated_cnpj_round_trips_through_validation() {
+        let generated = CNPJDocument::generate(Some(7));
+
+        assert_eq!(CNPJDocument::validate_input(&generated.digits()), None);
+        assert!(is_valid_cnpj(&generated.digits()));
+    }
+
+    #[test]
+    fn bare_and_masked_input_agree_once_parsed() {
+        let bare = CNPJDocument::try_from("03165685000114".to_string()).unwrap();
+        let masked = CNPJDocument::try_from("03.165.685/0001-14".to_string()).unwrap();
+
+        assert_eq!(bare.formatted(), masked.formatted());
+        assert_eq!(bare.digits(), masked.digits());
+    }
+
+    #[test_case("12345", "12.345"           ; "partial group is left unmasked")]
+    #[test_case("1234567", "12.345.67"      ; "second group triggers its separator")]
+    fn format_masks_progressively(partial: &str, expected: &str) {
+        assert_eq!(super::format(partial), expected);
     }
 }
\ No newline at end of file
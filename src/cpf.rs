@@ -1,19 +1,50 @@
 use std::fmt::Display;
-use crate::{all_equal, DocumentValidator, ErrorKind, to_integer_vector};
+use nom::branch::alt;
+use nom::combinator::{all_consuming, map};
+use nom::sequence::tuple;
+use nom::IResult;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::parsing::{classify_failure, digit_group, separator};
+use crate::{all_equal, numbers_to_string, DocumentValidator, ErrorKind, InvalidDocumentReason};
 
 const CPF_SIZE: usize = 11;
+const CPF_MASKED_SIZE: usize = 14;
+const CPF_MASK_SEPARATORS: [char; 2] = ['.', '-'];
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CPFDocument(String);
 
+/// The Receita Federal fiscal region a CPF was issued under, encoded in its 9th digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FiscalRegion {
+    /// Rio Grande do Sul.
+    RS,
+    /// Distrito Federal, Goiás, Mato Grosso, Mato Grosso do Sul or Tocantins.
+    DfGoMsMt,
+    /// Acre, Amazonas, Amapá, Pará, Rondônia or Roraima.
+    AcAmApPaRoRr,
+    /// Ceará, Maranhão or Piauí.
+    CeMaPi,
+    /// Alagoas, Paraíba, Pernambuco or Rio Grande do Norte.
+    AlPbPeRn,
+    /// Bahia or Sergipe.
+    BaSe,
+    /// Minas Gerais.
+    MG,
+    /// Espírito Santo or Rio de Janeiro.
+    EsRj,
+    /// São Paulo.
+    SP,
+    /// Paraná or Santa Catarina.
+    PrSc,
+}
+
 impl TryFrom<String> for CPFDocument {
     type Error = ErrorKind;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        CPFDocument::validate_input(&value)
-            .map_or_else(
-                || Ok(CPFDocument(value)),
-                |err| Err(err))
+        parse(&value).map(|digits| CPFDocument(numbers_to_string(&digits)))
     }
 }
 
@@ -21,20 +52,49 @@ impl DocumentValidator for CPFDocument {
     type Error = ErrorKind;
 
     fn validate_input(input: &str) -> Option<Self::Error> {
-        if input.len() != CPF_SIZE {
-            return Some(ErrorKind::InvalidInput)
-        }
+        parse(input).err()
+    }
+}
 
-        let input_as_integer_vector = to_integer_vector(input);
+/// Parses a CPF, masked (`ddd.ddd.ddd-dd`) or bare, returning its digits once they've
+/// passed the all-equal and check-digit validation.
+fn parse(input: &str) -> Result<Vec<u32>, ErrorKind> {
+    if input.is_empty() {
+        return Err(ErrorKind::Empty);
+    }
 
-        if input_as_integer_vector.len() != CPF_SIZE {
-            return Some(ErrorKind::InvalidCharacters)
-        }
-        if all_equal(&input_as_integer_vector) {
-            return Some(ErrorKind::InvalidDocument)
-        }
+    let digits = parse_digits(input)?;
 
-        validate_cpf(&input_as_integer_vector)
+    if all_equal(&digits) {
+        return Err(ErrorKind::InvalidDocument(InvalidDocumentReason::AllDigitsEqual));
+    }
+
+    match validate_cpf(&digits) {
+        Some(error) => Err(error),
+        None => Ok(digits),
+    }
+}
+
+fn cpf_masked(input: &str) -> IResult<&str, Vec<char>> {
+    map(
+        tuple((
+            digit_group(3), separator('.'),
+            digit_group(3), separator('.'),
+            digit_group(3), separator('-'),
+            digit_group(2),
+        )),
+        |(a, _, b, _, c, _, d)| [a, b, c, d].concat(),
+    )(input)
+}
+
+fn cpf_bare(input: &str) -> IResult<&str, Vec<char>> {
+    digit_group(CPF_SIZE)(input)
+}
+
+fn parse_digits(input: &str) -> Result<Vec<u32>, ErrorKind> {
+    match all_consuming(alt((cpf_masked, cpf_bare)))(input) {
+        Ok((_, chars)) => Ok(chars.into_iter().map(|c| c.to_digit(10).unwrap()).collect()),
+        Err(_) => Err(classify_failure(input, &CPF_MASK_SEPARATORS, &[CPF_SIZE, CPF_MASKED_SIZE])),
     }
 }
 
@@ -44,7 +104,78 @@ impl Display for CPFDocument {
     }
 }
 
-fn validate_cpf(numbers: &Vec<u32>) -> Option<ErrorKind> {
+impl CPFDocument {
+    /// Returns the document formatted with the canonical CPF mask, `ddd.ddd.ddd-dd`.
+    pub fn formatted(&self) -> String {
+        format(&self.0)
+    }
+
+    /// Returns the document's bare digits, stripped of any mask.
+    pub fn digits(&self) -> String {
+        self.0.clone()
+    }
+
+    /// Returns the Receita Federal fiscal region this CPF was issued under,
+    /// encoded in its 9th digit.
+    pub fn fiscal_region(&self) -> FiscalRegion {
+        let region_digit = self.0.chars().nth(8).and_then(|c| c.to_digit(10)).unwrap();
+
+        match region_digit {
+            0 => FiscalRegion::RS,
+            1 => FiscalRegion::DfGoMsMt,
+            2 => FiscalRegion::AcAmApPaRoRr,
+            3 => FiscalRegion::CeMaPi,
+            4 => FiscalRegion::AlPbPeRn,
+            5 => FiscalRegion::BaSe,
+            6 => FiscalRegion::MG,
+            7 => FiscalRegion::EsRj,
+            8 => FiscalRegion::SP,
+            9 => FiscalRegion::PrSc,
+            _ => unreachable!("a validated CPF only contains digits 0-9"),
+        }
+    }
+
+    /// Generates a random, structurally valid CPF.
+    ///
+    /// Pass `Some(seed)` to make the generation deterministic, which is useful for
+    /// seeding tests and fixtures.
+    pub fn generate(seed: Option<u64>) -> Self {
+        let mut rng = seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+
+        let base = loop {
+            let candidate: Vec<u32> = (0..9).map(|_| rng.gen_range(0..10)).collect();
+
+            if !all_equal(&candidate) {
+                break candidate;
+            }
+        };
+
+        let (first_digit, second_digit) = calculate_check_digits(&base);
+        let digits: Vec<u32> = base.into_iter().chain([first_digit, second_digit]).collect();
+
+        CPFDocument(numbers_to_string(&digits))
+    }
+}
+
+/// Progressively masks a partial CPF digit string as the user types it,
+/// e.g. `"12345"` becomes `"123.45"`.
+pub fn format(partial: &str) -> String {
+    let digits: String = partial.chars().filter(|c| c.is_ascii_digit()).take(CPF_SIZE).collect();
+    let mut result = String::with_capacity(14);
+
+    for (index, character) in digits.chars().enumerate() {
+        match index {
+            3 | 6 => result.push('.'),
+            9 => result.push('-'),
+            _ => {}
+        }
+        result.push(character);
+    }
+
+    result
+}
+
+fn calculate_check_digits(first_nine_digits: &[u32]) -> (u32, u32) {
     fn digit_calculation(t: (u32, u32), curr: &u32) -> (u32, u32) {
         (t.0 + t.1 * curr, t.1 - 1)
     }
@@ -55,8 +186,6 @@ fn validate_cpf(numbers: &Vec<u32>) -> Option<ErrorKind> {
         }
     }
 
-    let first_nine_digits = &numbers[..9];
-
     let first_digit = ten_to_zero(first_nine_digits
             .iter()
             .fold((0, 10), digit_calculation).0 * 10 % 11
@@ -68,9 +197,75 @@ fn validate_cpf(numbers: &Vec<u32>) -> Option<ErrorKind> {
 
     let second_digit = ten_to_zero((second_digit + first_digit * curr) * 10 % 11);
 
-    if numbers[numbers.len() - 2] == first_digit && numbers[numbers.len() - 1] == second_digit {
+    (first_digit, second_digit)
+}
+
+fn validate_cpf(numbers: &[u32]) -> Option<ErrorKind> {
+    let (expected_first_digit, expected_second_digit) = calculate_check_digits(&numbers[..9]);
+    let actual_first_digit = numbers[numbers.len() - 2];
+    let actual_second_digit = numbers[numbers.len() - 1];
+
+    if actual_first_digit == expected_first_digit && actual_second_digit == expected_second_digit {
         None
     } else {
-        Some(ErrorKind::InvalidDocument)
+        Some(ErrorKind::InvalidDocument(InvalidDocumentReason::CheckDigitMismatch {
+            expected: (expected_first_digit, expected_second_digit),
+            actual: (actual_first_digit, actual_second_digit),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CPFDocument, FiscalRegion};
+    use crate::br_document::is_valid_cpf;
+    use crate::DocumentValidator;
+    use test_case::test_case;
+
+    #[test]
+    fn generate_with_the_same_seed_is_deterministic() {
+        let first = CPFDocument::generate(Some(42));
+        let second = CPFDocument::generate(Some(42));
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn generated_cpf_round_trips_through_validation() {
+        let generated = CPFDocument::generate(Some(7));
+
+        assert_eq!(CPFDocument::validate_input(&generated.digits()), None);
+        assert!(is_valid_cpf(&generated.digits()));
+    }
+
+    #[test]
+    fn bare_and_masked_input_agree_once_parsed() {
+        let bare = CPFDocument::try_from("96865090039".to_string()).unwrap();
+        let masked = CPFDocument::try_from("968.650.900-39".to_string()).unwrap();
+
+        assert_eq!(bare.formatted(), masked.formatted());
+        assert_eq!(bare.digits(), masked.digits());
+    }
+
+    #[test_case("12345", "123.45"          ; "partial group is left unmasked")]
+    #[test_case("1234567", "123.456.7"     ; "third group triggers its separator")]
+    fn format_masks_progressively(partial: &str, expected: &str) {
+        assert_eq!(super::format(partial), expected);
+    }
+
+    #[test_case("12345678062", FiscalRegion::RS            ; "digit 0 is RS")]
+    #[test_case("12345678143", FiscalRegion::DfGoMsMt       ; "digit 1 is DfGoMsMt")]
+    #[test_case("12345678224", FiscalRegion::AcAmApPaRoRr    ; "digit 2 is AcAmApPaRoRr")]
+    #[test_case("12345678305", FiscalRegion::CeMaPi          ; "digit 3 is CeMaPi")]
+    #[test_case("12345678496", FiscalRegion::AlPbPeRn        ; "digit 4 is AlPbPeRn")]
+    #[test_case("12345678577", FiscalRegion::BaSe            ; "digit 5 is BaSe")]
+    #[test_case("12345678658", FiscalRegion::MG              ; "digit 6 is MG")]
+    #[test_case("12345678739", FiscalRegion::EsRj            ; "digit 7 is EsRj")]
+    #[test_case("12345678810", FiscalRegion::SP              ; "digit 8 is SP")]
+    #[test_case("12345678909", FiscalRegion::PrSc            ; "digit 9 is PrSc")]
+    fn fiscal_region_maps_the_ninth_digit(input: &str, expected: FiscalRegion) {
+        let document = CPFDocument::try_from(input.to_string()).unwrap();
+
+        assert_eq!(document.fiscal_region(), expected);
     }
 }
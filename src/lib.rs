@@ -1,31 +1,75 @@
+use std::fmt::{self, Display};
+use std::error::Error;
+
 pub mod br_document;
 pub mod cpf;
 pub mod cnpj;
+mod parsing;
 
 /// Enum that represents possible errors during validation of a document
 #[derive(Debug, Clone, PartialEq)]
 pub enum ErrorKind {
-    /// Indicates that the input string contained invalid characters.
-    InvalidCharacters,
-    /// Indicates that the provided document had a valid characters but did not pass validation.
-    InvalidDocument,
+    /// Indicates that the input string was empty.
+    Empty,
+    /// Indicates that the input string contained an invalid character, carrying the byte
+    /// index and the offending character so callers can point at exactly where parsing failed.
+    InvalidCharacters { index: usize, character: char },
+    /// Indicates that the provided document had valid characters but did not pass validation.
+    InvalidDocument(InvalidDocumentReason),
     /// Indicates that the input was not valid and validation did not even occur.
     InvalidInput,
 }
 
+/// Describes why a document with otherwise valid characters failed validation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidDocumentReason {
+    /// Every digit in the document was the same, which can never be a valid document.
+    AllDigitsEqual,
+    /// The document's check digits did not match the ones computed from its base digits.
+    CheckDigitMismatch { expected: (u32, u32), actual: (u32, u32) },
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Empty => write!(f, "the input string was empty"),
+            ErrorKind::InvalidCharacters { index, character } => {
+                write!(f, "invalid character '{character}' at position {index}")
+            }
+            ErrorKind::InvalidDocument(reason) => write!(f, "{reason}"),
+            ErrorKind::InvalidInput => write!(f, "the input was not valid and validation did not even occur"),
+        }
+    }
+}
+
+impl Display for InvalidDocumentReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidDocumentReason::AllDigitsEqual => write!(f, "all digits in the document are the same"),
+            InvalidDocumentReason::CheckDigitMismatch { expected, actual } => write!(
+                f,
+                "check digits {:?} did not match the expected {:?}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl Error for ErrorKind {}
+
 pub trait DocumentValidator : Sized {
     type Error;
 
     fn validate_input(input: &str) -> Option<Self::Error>;
 }
 
-pub(crate) fn to_integer_vector(input: &str) -> Vec<u32> {
-    input.chars().map_while(|c| c.to_digit(10)).collect()
-}
-
-pub(crate) fn all_equal(n: &Vec<u32>) -> bool {
+pub(crate) fn all_equal(n: &[u32]) -> bool {
     n[1..].iter().try_fold(n[0], |prev, el| match prev {
         a if a == *el => Some(*el),
         _ => None,
     }).is_some()
 }
+
+pub(crate) fn numbers_to_string(n: &[u32]) -> String {
+    n.iter().map(|n| char::from_digit(*n, 10).unwrap()).collect()
+}
@@ -0,0 +1,37 @@
+use nom::character::complete::{char as nom_char, satisfy};
+use nom::multi::many_m_n;
+use nom::IResult;
+
+use crate::ErrorKind;
+
+fn digit(input: &str) -> IResult<&str, char> {
+    satisfy(|c: char| c.is_ascii_digit())(input)
+}
+
+/// Parses exactly `n` consecutive ASCII digits, e.g. `digit_group(3)` parses
+/// `"123"` out of `"123.456"`.
+pub(crate) fn digit_group(n: usize) -> impl FnMut(&str) -> IResult<&str, Vec<char>> {
+    move |input: &str| many_m_n(n, n, digit)(input)
+}
+
+/// Matches a single mask separator character, e.g. `.`, `/` or `-`.
+pub(crate) fn separator(c: char) -> impl FnMut(&str) -> IResult<&str, char> {
+    move |input: &str| nom_char(c)(input)
+}
+
+/// Builds the `ErrorKind` for a failed parse. Only attempts to pin down an offending
+/// character when `input`'s length matches one of this document type's own
+/// `recognized_lengths` (its bare or masked size) — otherwise the input is simply the
+/// wrong shape for this type entirely (e.g. a CNPJ tried against the CPF parser), and
+/// `InvalidInput` lets the caller fall through to the right type instead of misreporting
+/// one of its mask separators as an invalid character.
+pub(crate) fn classify_failure(input: &str, separators: &[char], recognized_lengths: &[usize]) -> ErrorKind {
+    if !recognized_lengths.contains(&input.chars().count()) {
+        return ErrorKind::InvalidInput;
+    }
+
+    match input.char_indices().find(|(_, c)| !c.is_ascii_digit() && !separators.contains(c)) {
+        Some((index, character)) => ErrorKind::InvalidCharacters { index, character },
+        None => ErrorKind::InvalidInput,
+    }
+}